@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use ecu_diagnostics::channel::CanFrame;
+
+/// Byte ordering of a signal within its message payload, as encoded in the
+/// `SG_` record (`1` = Intel/little-endian, `0` = Motorola/big-endian).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    Motorola,
+    Intel
+}
+
+/// A single `SG_` signal definition belonging to a [`Message`].
+#[derive(Debug, Clone)]
+pub struct Signal {
+    pub name: String,
+    pub start_bit: u32,
+    pub length: u32,
+    pub byte_order: ByteOrder,
+    pub is_signed: bool,
+    pub scale: f64,
+    pub offset: f64,
+    pub unit: String
+}
+
+/// A single `BO_` message definition and the signals it carries.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub id: u32,
+    pub name: String,
+    pub dlc: u8,
+    pub signals: Vec<Signal>
+}
+
+/// A decoded signal, ready to be rendered under a raw CAN frame.
+#[derive(Debug, Clone)]
+pub struct DecodedSignal {
+    pub name: String,
+    pub value: f64,
+    pub unit: String
+}
+
+/// A parsed DBC database, keyed by CAN message ID for fast lookup while tracing.
+#[derive(Debug, Clone, Default)]
+pub struct DbcFile {
+    messages: HashMap<u32, Message>
+}
+
+impl DbcFile {
+    /// Parses a DBC file from disk, keeping only the `BO_`/`SG_` records needed
+    /// to decode frame payloads (everything else - comments, attributes, value
+    /// tables - is ignored).
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<DbcFile> {
+        let text = fs::read_to_string(path)?;
+        Ok(DbcFile::parse(&text))
+    }
+
+    pub fn parse(text: &str) -> DbcFile {
+        let mut messages: HashMap<u32, Message> = HashMap::new();
+        let mut last_id: Option<u32> = None;
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.starts_with("BO_ ") {
+                if let Some(msg) = parse_bo_line(line) {
+                    last_id = Some(msg.id);
+                    messages.insert(msg.id, msg);
+                } else {
+                    last_id = None;
+                }
+            } else if line.starts_with("SG_ ") {
+                if let Some(id) = last_id {
+                    if let Some(signal) = parse_sg_line(line) {
+                        if let Some(msg) = messages.get_mut(&id) {
+                            msg.signals.push(signal);
+                        }
+                    }
+                }
+            }
+        }
+        DbcFile { messages }
+    }
+
+    /// Decodes every signal of the `BO_` whose id matches `frame.get_address()`.
+    /// Returns an empty vec if the frame's ID isn't present in the database.
+    pub fn decode_frame(&self, frame: &CanFrame) -> Vec<DecodedSignal> {
+        let msg = match self.messages.get(&frame.get_address()) {
+            Some(m) => m,
+            None => return Vec::new()
+        };
+        let data = frame.get_data();
+        msg.signals.iter().map(|sig| DecodedSignal {
+            name: sig.name.clone(),
+            value: decode_signal(sig, data),
+            unit: sig.unit.clone()
+        }).collect()
+    }
+}
+
+/// `BO_ <id> <name>: <dlc> <transmitter>`
+fn parse_bo_line(line: &str) -> Option<Message> {
+    let rest = line.strip_prefix("BO_ ")?.trim();
+    let (header, _transmitter) = rest.split_once(':')?;
+    let mut header_parts = header.split_whitespace();
+    // DBC stores extended message IDs with bit 31 set (`id | 0x80000000`);
+    // normalize to the raw 29-bit arbitration ID here so `decode_frame` can
+    // look messages up directly by `frame.get_address()`.
+    let id: u32 = header_parts.next()?.parse::<u32>().ok()? & 0x1FFF_FFFF;
+    let name = header_parts.next()?.to_string();
+    let dlc: u8 = rest.split(':').nth(1)?.trim().split_whitespace().next()?.parse().ok()?;
+    Some(Message { id, name, dlc, signals: Vec::new() })
+}
+
+/// ` SG_ <name> : <start>|<length>@<order><sign> (<scale>,<offset>) [<min>|<max>] "<unit>" <receivers>`
+fn parse_sg_line(line: &str) -> Option<Signal> {
+    let rest = line.strip_prefix("SG_ ")?.trim();
+    let (name, rest) = rest.split_once(':')?;
+    let rest = rest.trim();
+
+    let mut parts = rest.splitn(2, ' ');
+    let layout = parts.next()?;
+    let tail = parts.next()?.trim();
+
+    let (bit_spec, rest) = layout.split_once('@')?;
+    let (start_str, len_str) = bit_spec.split_once('|')?;
+    let start_bit: u32 = start_str.parse().ok()?;
+    let length: u32 = len_str.parse().ok()?;
+
+    let mut chars = rest.chars();
+    let order_char = chars.next()?;
+    let sign_char = chars.next()?;
+    let byte_order = if order_char == '1' { ByteOrder::Intel } else { ByteOrder::Motorola };
+    let is_signed = sign_char == '-';
+
+    let factor_start = tail.find('(')?;
+    let factor_end = tail.find(')')?;
+    let (scale_str, offset_str) = tail[factor_start + 1..factor_end].split_once(',')?;
+    let scale: f64 = scale_str.trim().parse().ok()?;
+    let offset: f64 = offset_str.trim().parse().ok()?;
+
+    let unit = tail.split('"').nth(1).unwrap_or("").to_string();
+
+    Some(Signal {
+        name: name.trim().to_string(),
+        start_bit,
+        length,
+        byte_order,
+        is_signed,
+        scale,
+        offset,
+        unit
+    })
+}
+
+/// Extracts and sign-extends a signal's raw integer from an 8-byte payload,
+/// then applies `physical = raw * scale + offset`.
+fn decode_signal(sig: &Signal, data: &[u8]) -> f64 {
+    let mut raw: u64 = 0;
+
+    match sig.byte_order {
+        ByteOrder::Intel => {
+            for i in 0..sig.length {
+                let bit_pos = sig.start_bit + i;
+                let byte_idx = (bit_pos / 8) as usize;
+                let bit_idx = bit_pos % 8;
+                if byte_idx >= data.len() {
+                    break;
+                }
+                let bit = (data[byte_idx] >> bit_idx) & 1;
+                raw |= (bit as u64) << i;
+            }
+        },
+        ByteOrder::Motorola => {
+            // Motorola start-bit numbering runs MSB-first within each byte, so
+            // walk bits decrementing through the byte layout.
+            let mut byte_idx = (sig.start_bit / 8) as usize;
+            let mut bit_idx = sig.start_bit % 8;
+            for i in 0..sig.length {
+                if byte_idx >= data.len() {
+                    break;
+                }
+                let bit = (data[byte_idx] >> bit_idx) & 1;
+                raw |= (bit as u64) << (sig.length - 1 - i);
+                if bit_idx == 0 {
+                    bit_idx = 7;
+                    byte_idx += 1;
+                } else {
+                    bit_idx -= 1;
+                }
+            }
+        }
+    }
+
+    let value = if sig.length == 0 {
+        // Malformed `SG_` record (zero-length signal) - nothing to decode.
+        0.0
+    } else if sig.is_signed && sig.length < 64 {
+        let sign_bit = 1u64 << (sig.length - 1);
+        if raw & sign_bit != 0 {
+            (raw as i64 - (1i64 << sig.length)) as f64
+        } else {
+            raw as f64
+        }
+    } else {
+        raw as f64
+    };
+
+    value * sig.scale + sig.offset
+}
@@ -0,0 +1,196 @@
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use ecu_diagnostics::channel::{CanChannel, CanFrame, ChannelResult, ChannelError, IsoTPChannel, IsoTPSettings};
+
+/// Type-erased handle to the hardware backend that launched the session.
+/// SocketCAN (`open_socketcan`) is the only backend that builds one; there is
+/// no J2534/Passthru backend behind it. Pages only ever talk to `DynHardware`,
+/// so they do not depend on which backend is underneath.
+#[derive(Clone)]
+pub struct DynHardware {
+    inner: Arc<Mutex<dyn HardwareBackend>>
+}
+
+/// Object-safe subset of hardware capabilities a `DynHardware` can expose.
+trait HardwareBackend: Send {
+    fn create_can_channel(&mut self) -> ChannelResult<Box<dyn CanChannel>>;
+    fn create_iso_tp_channel(&mut self) -> ChannelResult<Box<dyn IsoTPChannel>>;
+}
+
+impl DynHardware {
+    fn new(backend: impl HardwareBackend + 'static) -> Self {
+        Self { inner: Arc::new(Mutex::new(backend)) }
+    }
+
+    pub fn create_can_channel(&self) -> ChannelResult<Box<dyn CanChannel>> {
+        self.inner.lock().unwrap().create_can_channel()
+    }
+
+    pub fn create_iso_tp_channel(&self) -> ChannelResult<Box<dyn IsoTPChannel>> {
+        self.inner.lock().unwrap().create_iso_tp_channel()
+    }
+
+    /// Opens a raw SocketCAN binding on `iface` (e.g. `can0`, `vcan0`) and wraps
+    /// it as a `DynHardware`, so `CanTracerPage` and friends work unchanged.
+    pub fn open_socketcan(iface: &str) -> ChannelResult<DynHardware> {
+        Ok(DynHardware::new(SocketCanHardware::open(iface)?))
+    }
+}
+
+/// Backed by a single SocketCAN interface. Unlike a Passthru device, the bus
+/// speed is configured outside the app (`ip link set can0 type can bitrate ...`),
+/// so there's no equivalent handshake to perform when opening it here.
+struct SocketCanHardware {
+    iface: String
+}
+
+impl SocketCanHardware {
+    fn open(iface: &str) -> ChannelResult<SocketCanHardware> {
+        // Probe eagerly so a bad interface name fails at launch time rather than
+        // on the first read/write once `CanTracerPage` is already showing.
+        socketcan::CANSocket::open(iface)?;
+        Ok(SocketCanHardware { iface: iface.to_string() })
+    }
+}
+
+impl HardwareBackend for SocketCanHardware {
+    fn create_can_channel(&mut self) -> ChannelResult<Box<dyn CanChannel>> {
+        Ok(Box::new(SocketCanChannel { iface: self.iface.clone(), socket: None }))
+    }
+
+    fn create_iso_tp_channel(&mut self) -> ChannelResult<Box<dyn IsoTPChannel>> {
+        Ok(Box::new(SocketCanIsoTpChannel {
+            iface: self.iface.clone(),
+            settings: IsoTPSettings::default(),
+            tx_id: 0,
+            rx_id: 0,
+            socket: None
+        }))
+    }
+}
+
+struct SocketCanChannel {
+    iface: String,
+    socket: Option<socketcan::CANSocket>
+}
+
+impl CanChannel for SocketCanChannel {
+    fn set_can_cfg(&mut self, _baud: u32, _use_ext: bool) -> ChannelResult<()> {
+        // No-op: the bus speed is a property of the kernel interface, not
+        // something this process can change.
+        Ok(())
+    }
+
+    fn open(&mut self) -> ChannelResult<()> {
+        let socket = socketcan::CANSocket::open(&self.iface)?;
+        socket.set_read_timeout(std::time::Duration::from_millis(100))?;
+        self.socket = Some(socket);
+        Ok(())
+    }
+
+    fn close(&mut self) -> ChannelResult<()> {
+        self.socket = None;
+        Ok(())
+    }
+
+    fn read_packets(&mut self, timeout_ms: u32, max: usize) -> ChannelResult<Vec<CanFrame>> {
+        let socket = match &self.socket {
+            Some(s) => s,
+            None => return Ok(Vec::new())
+        };
+        let deadline = Instant::now() + std::time::Duration::from_millis(timeout_ms as u64);
+        let mut out = Vec::new();
+        while out.len() < max && Instant::now() < deadline {
+            match socket.read_frame() {
+                Ok(f) => out.push(CanFrame::new(f.id(), f.data(), f.is_extended())),
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock || e.kind() == std::io::ErrorKind::TimedOut => break,
+                Err(e) => return Err(e.into())
+            }
+        }
+        Ok(out)
+    }
+
+    fn write_packets(&mut self, packets: Vec<CanFrame>, _timeout_ms: u32) -> ChannelResult<()> {
+        let socket = match &self.socket {
+            Some(s) => s,
+            None => return Ok(())
+        };
+        for p in packets {
+            // `CANFrame::new`'s third argument is `rtr`, not "extended" - the
+            // socketcan crate derives the EFF flag from the id itself, so an
+            // extended frame just needs its full 29-bit id passed through.
+            let frame = socketcan::CANFrame::new(p.get_address(), p.get_data(), false, false)
+                .map_err(|_| ChannelError::UnsupportedRequest)?;
+            socket.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// Backed by a Linux `CAN_ISOTP` socket bound to the same interface a
+/// `SocketCanChannel` would use. Configuration (`set_iso_tp_cfg`/`set_ids`) is
+/// only staged here; the kernel socket isn't opened until `open()`, mirroring
+/// how `SocketCanChannel` defers the bus socket until then.
+struct SocketCanIsoTpChannel {
+    iface: String,
+    settings: IsoTPSettings,
+    tx_id: u32,
+    rx_id: u32,
+    socket: Option<socketcan_isotp::IsoTpSocket>
+}
+
+impl IsoTPChannel for SocketCanIsoTpChannel {
+    fn set_iso_tp_cfg(&mut self, cfg: IsoTPSettings) -> ChannelResult<()> {
+        self.settings = cfg;
+        Ok(())
+    }
+
+    fn set_ids(&mut self, tx_id: u32, rx_id: u32) -> ChannelResult<()> {
+        self.tx_id = tx_id;
+        self.rx_id = rx_id;
+        Ok(())
+    }
+
+    fn open(&mut self) -> ChannelResult<()> {
+        let opts = socketcan_isotp::IsoTpOptions::default()
+            .set_tx_padding(if self.settings.pad_frame { Some(0xCC) } else { None })
+            .set_ext_address(if self.settings.extended_addresses { Some(0x00) } else { None });
+        let flow_control = socketcan_isotp::FlowControlOptions::default()
+            .set_bs(self.settings.block_size)
+            .set_stmin(self.settings.st_min);
+        let socket = socketcan_isotp::IsoTpSocket::open_with_opts(
+            &self.iface,
+            self.rx_id,
+            self.tx_id,
+            opts,
+            flow_control,
+            None
+        ).map_err(|_| ChannelError::UnsupportedRequest)?;
+        self.socket = Some(socket);
+        Ok(())
+    }
+
+    fn close(&mut self) -> ChannelResult<()> {
+        self.socket = None;
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, timeout_ms: u32) -> ChannelResult<Vec<u8>> {
+        let socket = match &self.socket {
+            Some(s) => s,
+            None => return Err(ChannelError::UnsupportedRequest)
+        };
+        socket.set_read_timeout(std::time::Duration::from_millis(timeout_ms as u64))?;
+        Ok(socket.read().map(|buf| buf.to_vec())?)
+    }
+
+    fn write_bytes(&mut self, _addr: u32, _ext: bool, buffer: &[u8], timeout_ms: u32) -> ChannelResult<()> {
+        let socket = match &self.socket {
+            Some(s) => s,
+            None => return Err(ChannelError::UnsupportedRequest)
+        };
+        socket.set_write_timeout(std::time::Duration::from_millis(timeout_ms as u64))?;
+        Ok(socket.write(buffer)?)
+    }
+}
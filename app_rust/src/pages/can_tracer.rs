@@ -1,9 +1,10 @@
-use std::{borrow::BorrowMut, collections::HashMap, thread::JoinHandle, sync::Arc, time::Instant};
+use std::{borrow::BorrowMut, collections::HashMap, thread::JoinHandle, sync::Arc, time::{Instant, SystemTime}};
 
 use ecu_diagnostics::{channel::{CanChannel, ChannelResult, CanFrame, Packet}, hardware::HardwareResult};
 use egui::{Color32, plot::{Plot, Corner, Legend, Values}, Label, Sense};
 
-use crate::{window::{InterfacePage, StatusBar, PageAction}, dyn_hw::DynHardware};
+use crate::{window::{InterfacePage, StatusBar, PageAction}, dyn_hw::DynHardware, dbc::DbcFile};
+use crate::trace::{self, TraceFrame};
 
 use super::status_bar::MainStatusBar;
 
@@ -48,6 +49,17 @@ pub struct CanTracerPage {
     tx_id_str: String,
     tx_data_str: String,
     tx_can_data: (u32,Vec<u8>),
+    dbc: Option<DbcFile>,
+    dbc_error: Option<String>,
+    recording: bool,
+    record_start: Option<Instant>,
+    record_start_epoch: Option<SystemTime>,
+    record_buf: Vec<TraceFrame>,
+    replay_frames: Vec<TraceFrame>,
+    replaying: bool,
+    replay_start: Option<Instant>,
+    replay_idx: usize,
+    trace_error: Option<String>,
     //handle: Option<JoinHandle<()>>,
 }
 
@@ -75,6 +87,17 @@ impl CanTracerPage {
             tx_data_str: "01 02 03 04 05 06 07 08".into(),
             tx_bin_str: "".into(),
             tx_can_data: (0x0001, vec![0,0,0,0,0,0,0,0]),
+            dbc: None,
+            dbc_error: None,
+            recording: false,
+            record_start: None,
+            record_start_epoch: None,
+            record_buf: Vec::new(),
+            replay_frames: Vec::new(),
+            replaying: false,
+            replay_start: None,
+            replay_idx: 0,
+            trace_error: None,
             //handle: None
         }
     }
@@ -108,6 +131,81 @@ impl InterfacePage for CanTracerPage {
                 self.mask_str = "0000".into();
             }
 
+            ui.horizontal(|row| {
+                if row.button("Load DBC").clicked() {
+                    match rfd::FileDialog::new().add_filter("DBC database", &["dbc"]).pick_file() {
+                        Some(path) => match DbcFile::load(&path) {
+                            Ok(db) => {
+                                self.dbc = Some(db);
+                                self.dbc_error = None;
+                            },
+                            Err(e) => self.dbc_error = Some(format!("Could not load DBC: {}", e))
+                        },
+                        None => {}
+                    }
+                }
+                if self.dbc.is_some() {
+                    row.label("DBC loaded");
+                }
+            });
+            if let Some(e) = &self.dbc_error {
+                ui.colored_label(Color32::from_rgb(255,0,0), e);
+            }
+
+            ui.horizontal(|row| {
+                let label = if self.recording { "Stop recording" } else { "Start recording" };
+                if row.button(label).clicked() {
+                    self.recording = !self.recording;
+                    if self.recording {
+                        self.record_start = Some(Instant::now());
+                        self.record_start_epoch = Some(SystemTime::now());
+                        self.record_buf.clear();
+                    }
+                }
+                if row.button("Export").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("candump log", &["log"])
+                        .add_filter("Vector ASC", &["asc"])
+                        .save_file() {
+                        let is_asc = path.extension().map(|e| e == "asc").unwrap_or(false);
+                        let contents = if is_asc {
+                            trace::write_asc(&self.record_buf, 1)
+                        } else {
+                            trace::write_candump(&self.record_buf, "can0", self.record_start_epoch.unwrap_or(SystemTime::now()))
+                        };
+                        if let Err(e) = std::fs::write(&path, contents) {
+                            self.trace_error = Some(format!("Could not export trace: {}", e));
+                        } else {
+                            self.trace_error = None;
+                        }
+                    }
+                }
+                if row.button("Import & Replay").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("candump log", &["log"])
+                        .add_filter("Vector ASC", &["asc"])
+                        .pick_file() {
+                        match std::fs::read_to_string(&path) {
+                            Ok(text) => {
+                                let is_asc = path.extension().map(|e| e == "asc").unwrap_or(false);
+                                self.replay_frames = if is_asc { trace::parse_asc(&text) } else { trace::parse_candump(&text) };
+                                self.replay_idx = 0;
+                                self.replaying = !self.replay_frames.is_empty();
+                                self.replay_start = Some(Instant::now());
+                                self.trace_error = None;
+                            },
+                            Err(e) => self.trace_error = Some(format!("Could not read trace: {}", e))
+                        }
+                    }
+                }
+                if self.recording {
+                    row.label(format!("{} frames captured", self.record_buf.len()));
+                }
+            });
+            if let Some(e) = &self.trace_error {
+                ui.colored_label(Color32::from_rgb(255,0,0), e);
+            }
+
             if !self.mask_str.is_empty() {
                 if let Ok(parse) = u32::from_str_radix(&self.mask_str, 16) {
                     self.mask = parse;
@@ -206,6 +304,31 @@ impl InterfacePage for CanTracerPage {
                         Vec::new()
                     }
                 });
+                if self.recording {
+                    if let Some(start) = self.record_start {
+                        for f in &frames {
+                            self.record_buf.push(TraceFrame { offset: start.elapsed(), frame: f.clone() });
+                        }
+                    }
+                }
+
+                if self.replaying {
+                    if let Some(start) = self.replay_start {
+                        let elapsed = start.elapsed();
+                        while self.replay_idx < self.replay_frames.len()
+                            && self.replay_frames[self.replay_idx].offset <= elapsed {
+                            let tf = &self.replay_frames[self.replay_idx];
+                            if let Err(e) = can_channel.write_packets(vec![tf.frame.clone()], 50) {
+                                self.trace_error = Some(format!("Error replaying frame: {}", e));
+                            }
+                            self.replay_idx += 1;
+                        }
+                        if self.replay_idx >= self.replay_frames.len() {
+                            self.replaying = false;
+                        }
+                    }
+                }
+
                 let num = frames.len() as f32;
                 for frame in frames {
                     self.can_map.insert(frame.get_address(), frame);
@@ -225,6 +348,11 @@ impl InterfacePage for CanTracerPage {
                             self.mask_str = "FFFF".into();
                             self.filt_str = format!("{:04X}", f.get_address());
                         }
+                        if let Some(dbc) = &self.dbc {
+                            for signal in dbc.decode_frame(f) {
+                                ui.label(format!("    {} = {} {}", signal.name, signal.value, signal.unit));
+                            }
+                        }
                     }
                 }
                 let line = egui::plot::Line::new(Values::from_ys_f32(&self.act_map[0..self.events_draw]));
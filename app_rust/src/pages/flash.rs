@@ -0,0 +1,255 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use ecu_diagnostics::{
+    channel::IsoTPSettings,
+    uds::{UdsDiagnosticServer, UdsServerOptions},
+};
+use egui::Color32;
+
+use crate::{window::{InterfacePage, StatusBar, PageAction}, dyn_hw::DynHardware};
+
+use super::status_bar::MainStatusBar;
+
+/// Progress of a block-based coredump read or firmware flash, updated from the
+/// background worker thread and polled by the UI each frame.
+#[derive(Debug, Clone)]
+pub enum TransferState {
+    Prepare,
+    ReadingBlock { id: usize, out_of: usize, bytes_written: usize },
+    Completed,
+    Failed(String)
+}
+
+/// Which direction the block transfer is going.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferKind {
+    ReadCoredump,
+    FlashFirmware
+}
+
+pub struct FlashPage {
+    hw: DynHardware,
+    status_bar: MainStatusBar,
+
+    ignition_off_confirmed: bool,
+
+    kind: TransferKind,
+    firmware_path: Option<PathBuf>,
+    coredump_out_path: String,
+    coredump_address_str: String,
+    coredump_size_str: String,
+
+    state: Arc<RwLock<TransferState>>,
+    worker: Option<thread::JoinHandle<()>>,
+}
+
+impl FlashPage {
+    pub fn new(dev: DynHardware, bar: MainStatusBar) -> Self {
+        Self {
+            hw: dev,
+            status_bar: bar,
+            ignition_off_confirmed: false,
+            kind: TransferKind::ReadCoredump,
+            firmware_path: None,
+            coredump_out_path: "coredump.elf".into(),
+            coredump_address_str: "00000000".into(),
+            coredump_size_str: "10000".into(),
+            state: Arc::new(RwLock::new(TransferState::Prepare)),
+            worker: None,
+        }
+    }
+
+    fn start_read_coredump(&mut self) {
+        let hw = self.hw.clone();
+        let state = self.state.clone();
+        let out_path = self.coredump_out_path.clone();
+        let mem_address = u32::from_str_radix(&self.coredump_address_str, 16).unwrap_or(0);
+        let mem_size = u32::from_str_radix(&self.coredump_size_str, 16).unwrap_or(0);
+        *state.write().unwrap() = TransferState::Prepare;
+
+        self.worker = Some(thread::spawn(move || {
+            let result = run_read_coredump(hw, &state, &out_path, mem_address, mem_size);
+            if let Err(e) = result {
+                *state.write().unwrap() = TransferState::Failed(e);
+            } else {
+                *state.write().unwrap() = TransferState::Completed;
+            }
+        }));
+    }
+
+    /// Reports whether a transfer is still running, reaping the worker thread
+    /// (resetting `self.worker` to `None`) once it has finished so a new
+    /// transfer can be started.
+    fn worker_active(&mut self) -> bool {
+        if let Some(handle) = &self.worker {
+            if handle.is_finished() {
+                self.worker = None;
+            }
+        }
+        self.worker.is_some()
+    }
+
+    fn start_flash(&mut self) {
+        let path = match &self.firmware_path {
+            Some(p) => p.clone(),
+            None => return
+        };
+        let hw = self.hw.clone();
+        let state = self.state.clone();
+        *state.write().unwrap() = TransferState::Prepare;
+
+        self.worker = Some(thread::spawn(move || {
+            let result = run_flash(hw, &state, &path);
+            if let Err(e) = result {
+                *state.write().unwrap() = TransferState::Failed(e);
+            } else {
+                *state.write().unwrap() = TransferState::Completed;
+            }
+        }));
+    }
+}
+
+fn open_uds(hw: &DynHardware) -> Result<UdsDiagnosticServer, String> {
+    let channel = hw.create_iso_tp_channel().map_err(|e| e.to_string())?;
+    UdsDiagnosticServer::new_over_iso_tp(
+        UdsServerOptions::default(),
+        channel,
+        IsoTPSettings::default(),
+        0x7E0,
+        0x7E8,
+    ).map_err(|e| e.to_string())
+}
+
+/// The UDS TransferData blockSequenceCounter starts at `0x01` on the first
+/// block of a transfer and wraps back to `0x00` (not `0x01`) after `0xFF`.
+fn block_sequence_counter(block_id: usize) -> u8 {
+    ((block_id % 256) + 1) as u8
+}
+
+/// Reads `mem_size` bytes starting at `mem_address` from the ECU over UDS via
+/// RequestUpload/TransferData/RequestTransferExit, chunking to whatever
+/// `maxNumberOfBlockLength` RequestUpload negotiates rather than a fixed size,
+/// and updating `state` as each block completes so the UI can render progress.
+fn run_read_coredump(hw: DynHardware, state: &Arc<RwLock<TransferState>>, out_path: &str, mem_address: u32, mem_size: u32) -> Result<(), String> {
+    let mut server = open_uds(&hw)?;
+
+    let block_size = server.request_upload(mem_address, mem_size).map_err(|e| e.to_string())? as usize;
+    let block_size = block_size.max(1);
+    let out_of = (mem_size as usize + block_size - 1) / block_size;
+    let mut out = Vec::with_capacity(mem_size as usize);
+
+    for block_id in 0..out_of {
+        *state.write().unwrap() = TransferState::ReadingBlock { id: block_id, out_of, bytes_written: out.len() };
+        let block = server.transfer_data(block_sequence_counter(block_id), &[]).map_err(|e| e.to_string())?;
+        out.extend_from_slice(&block);
+    }
+
+    server.request_transfer_exit().map_err(|e| e.to_string())?;
+    fs::write(out_path, &out).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Flashes `firmware_path` to the ECU over UDS using a
+/// RequestDownload/TransferData/RequestTransferExit sequence, chunking to
+/// whatever `maxNumberOfBlockLength` RequestDownload negotiates rather than a
+/// fixed size, and verifying each block's acknowledgement before advancing.
+fn run_flash(hw: DynHardware, state: &Arc<RwLock<TransferState>>, firmware_path: &std::path::Path) -> Result<(), String> {
+    let firmware = fs::read(firmware_path).map_err(|e| e.to_string())?;
+    let mut server = open_uds(&hw)?;
+
+    let block_size = server.request_download(firmware.len() as u32).map_err(|e| e.to_string())? as usize;
+    let block_size = block_size.max(1);
+
+    let out_of = (firmware.len() + block_size - 1) / block_size;
+    for (block_id, chunk) in firmware.chunks(block_size).enumerate() {
+        *state.write().unwrap() = TransferState::ReadingBlock {
+            id: block_id,
+            out_of,
+            bytes_written: block_id * block_size
+        };
+        server.transfer_data(block_sequence_counter(block_id), chunk).map_err(|e| e.to_string())?;
+    }
+
+    server.request_transfer_exit().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+impl InterfacePage for FlashPage {
+    fn make_ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::epi::Frame<'_>) -> PageAction {
+        if !self.ignition_off_confirmed {
+            ui.colored_label(Color32::from_rgb(255, 165, 0), "This will read/write ECU memory directly.");
+            ui.label("Confirm the ignition is OFF before continuing.");
+            if ui.button("Ignition is off, continue").clicked() {
+                self.ignition_off_confirmed = true;
+            }
+            return PageAction::None;
+        }
+
+        ui.horizontal(|row| {
+            row.selectable_value(&mut self.kind, TransferKind::ReadCoredump, "Read coredump");
+            row.selectable_value(&mut self.kind, TransferKind::FlashFirmware, "Flash firmware");
+        });
+
+        let worker_active = self.worker_active();
+        match self.kind {
+            TransferKind::ReadCoredump => {
+                ui.horizontal(|row| {
+                    row.label("Output path");
+                    row.text_edit_singleline(&mut self.coredump_out_path);
+                });
+                ui.horizontal(|row| {
+                    row.label("Memory address (hex)");
+                    row.text_edit_singleline(&mut self.coredump_address_str);
+                    row.label("Size in bytes (hex)");
+                    row.text_edit_singleline(&mut self.coredump_size_str);
+                });
+                if !worker_active && ui.button("Start read").clicked() {
+                    self.start_read_coredump();
+                }
+            },
+            TransferKind::FlashFirmware => {
+                if ui.button("Choose firmware file").clicked() {
+                    self.firmware_path = rfd::FileDialog::new().pick_file();
+                }
+                if let Some(p) = &self.firmware_path {
+                    ui.label(format!("Selected: {}", p.display()));
+                }
+                if !worker_active && self.firmware_path.is_some() && ui.button("Start flash").clicked() {
+                    self.start_flash();
+                }
+            }
+        }
+
+        let state = self.state.read().unwrap().clone();
+        match state {
+            TransferState::Prepare => {
+                ui.label("Preparing transfer...");
+            },
+            TransferState::ReadingBlock { id, out_of, bytes_written } => {
+                let frac = if out_of == 0 { 0.0 } else { (id + 1) as f32 / out_of as f32 };
+                ui.add(egui::ProgressBar::new(frac).animate(true)
+                    .text(format!("Block {}/{} ({} bytes)", id + 1, out_of, bytes_written)));
+                ui.ctx().request_repaint();
+            },
+            TransferState::Completed => {
+                ui.colored_label(Color32::from_rgb(0, 200, 0), "Transfer complete!");
+            },
+            TransferState::Failed(e) => {
+                ui.colored_label(Color32::from_rgb(255, 0, 0), format!("Transfer failed: {}", e));
+            }
+        }
+
+        PageAction::None
+    }
+
+    fn get_title(&self) -> &'static str {
+        "OpenVehicleDiag Coredump & Flash"
+    }
+
+    fn get_status_bar(&self) -> Option<Box<dyn StatusBar>> {
+        Some(Box::new(self.status_bar.clone()))
+    }
+}
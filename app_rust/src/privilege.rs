@@ -0,0 +1,91 @@
+#[cfg(target_os = "linux")]
+use std::fs;
+
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+#[cfg(unix)]
+use std::process::Command;
+
+/// `CAP_NET_RAW`, as defined by `linux/capability.h` - needed to bind a raw
+/// CAN socket on most distributions.
+const CAP_NET_RAW: u64 = 1 << 13;
+
+/// A launch-time failure specific to privilege elevation, kept distinct from
+/// `ComServerError`/missing-device errors so the launcher can show the user
+/// something actionable instead of a raw driver error string.
+#[derive(Debug, Clone)]
+pub struct PrivilegeError {
+    pub message: String
+}
+
+impl std::fmt::Display for PrivilegeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Returns `true` if the current process holds `CAP_NET_RAW` in its effective
+/// capability set, per `/proc/self/status`. Always `true` on non-Linux targets,
+/// where the underlying driver reports its own permission errors.
+#[cfg(target_os = "linux")]
+pub fn has_net_raw_capability() -> bool {
+    let status = match fs::read_to_string("/proc/self/status") {
+        Ok(s) => s,
+        // Can't tell - assume yes and let the socket() call itself fail.
+        Err(_) => return true
+    };
+    for line in status.lines() {
+        if let Some(hex) = line.strip_prefix("CapEff:") {
+            if let Ok(mask) = u64::from_str_radix(hex.trim(), 16) {
+                return mask & CAP_NET_RAW != 0;
+            }
+        }
+    }
+    true
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn has_net_raw_capability() -> bool {
+    true
+}
+
+/// Checks whether this process can open a raw CAN socket on `iface`, and if
+/// not, offers to re-exec the whole application under `sudo` (the user is
+/// prompted by `sudo` itself for their password). Returns an actionable error
+/// if elevation isn't possible or is declined.
+#[cfg(unix)]
+pub fn ensure_can_privileges(iface: &str) -> Result<(), PrivilegeError> {
+    if has_net_raw_capability() {
+        return Ok(());
+    }
+
+    let confirmed = rfd::MessageDialog::new()
+        .set_title("Elevated privileges required")
+        .set_description(&format!(
+            "Opening {} needs CAP_NET_RAW. Relaunch OpenVehicleDiag with sudo?", iface))
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+
+    if !confirmed {
+        return Err(PrivilegeError {
+            message: format!(
+                "insufficient permissions to open {} - run with sudo or grant CAP_NET_RAW", iface)
+        });
+    }
+
+    let exe = std::env::current_exe().map_err(|e| PrivilegeError {
+        message: format!("insufficient permissions to open {} and could not locate executable to relaunch: {}", iface, e)
+    })?;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Replaces this process; only returns if exec() itself failed.
+    let err = Command::new("sudo").arg(exe).args(args).exec();
+    Err(PrivilegeError {
+        message: format!("insufficient permissions to open {} - run with sudo or grant CAP_NET_RAW ({})", iface, err)
+    })
+}
+
+#[cfg(not(unix))]
+pub fn ensure_can_privileges(_iface: &str) -> Result<(), PrivilegeError> {
+    Ok(())
+}
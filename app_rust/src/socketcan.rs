@@ -0,0 +1,38 @@
+use std::fs;
+use std::path::Path;
+
+/// Linux `ARPHRD_CAN` interface type, as reported by `/sys/class/net/<iface>/type`.
+const ARPHRD_CAN: &str = "280";
+
+/// A CAN network interface discovered via SocketCAN (Linux only).
+///
+/// Unlike [`crate::passthru::PassthruDevice`] these need no vendor driver -
+/// any interface the kernel exposes under `/sys/class/net` with an `ARPHRD_CAN`
+/// link type is usable, including virtual buses such as `vcan0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SocketCanDevice {
+    pub name: String,
+}
+
+impl SocketCanDevice {
+    /// Enumerates all CAN interfaces currently visible to the kernel, up or down.
+    pub fn find_all() -> std::io::Result<Vec<SocketCanDevice>> {
+        let mut found = Vec::new();
+        let net_class = Path::new("/sys/class/net");
+        if !net_class.exists() {
+            // Not on Linux, or no interfaces at all - just report nothing.
+            return Ok(found);
+        }
+        for entry in fs::read_dir(net_class)? {
+            let entry = entry?;
+            let iface_type = fs::read_to_string(entry.path().join("type")).unwrap_or_default();
+            if iface_type.trim() == ARPHRD_CAN {
+                if let Some(name) = entry.file_name().to_str() {
+                    found.push(SocketCanDevice { name: name.to_string() });
+                }
+            }
+        }
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(found)
+    }
+}
@@ -0,0 +1,142 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ecu_diagnostics::channel::CanFrame;
+
+/// A single captured frame, timestamped relative to the start of the recording.
+#[derive(Debug, Clone)]
+pub struct TraceFrame {
+    pub offset: Duration,
+    pub frame: CanFrame
+}
+
+/// Renders a capture as a SocketCAN `candump` log, e.g.:
+/// `(1234567890.123456) can0 1A2#0102030405060708`
+///
+/// `record_start` anchors `tf.offset` to wall-clock time so the timestamps are
+/// real epoch times, matching what `candump`/`canplayer` expect, rather than
+/// seconds-since-recording-started.
+pub fn write_candump(frames: &[TraceFrame], iface: &str, record_start: SystemTime) -> String {
+    let mut out = String::new();
+    for tf in frames {
+        let since_epoch = (record_start + tf.offset).duration_since(UNIX_EPOCH).unwrap_or_default();
+        let secs = since_epoch.as_secs();
+        let micros = since_epoch.subsec_micros();
+        let data: String = tf.frame.get_data().iter().map(|b| format!("{:02X}", b)).collect();
+        // candump/canplayer identify standard vs. extended IDs by the width of
+        // the hex field (3 digits vs. 8), not its numeric value, so it must be
+        // zero-padded rather than printed with bare `{:X}`.
+        let id_str = if tf.frame.is_extended() {
+            format!("{:08X}", tf.frame.get_address())
+        } else {
+            format!("{:03X}", tf.frame.get_address())
+        };
+        out.push_str(&format!("({}.{:06}) {} {}#{}\n", secs, micros, iface, id_str, data));
+    }
+    out
+}
+
+/// Renders a capture as a Vector ASC log: relative seconds, channel, hex ID
+/// (with a trailing `x` for extended IDs), direction, DLC, then data bytes.
+pub fn write_asc(frames: &[TraceFrame], channel: u32) -> String {
+    let mut out = String::new();
+    out.push_str("date Thu Jan 1 00:00:00.000 1970\n");
+    out.push_str("base hex timestamps relative\n");
+    for tf in frames {
+        let secs = tf.offset.as_secs_f64();
+        let data = tf.frame.get_data();
+        let id_str = format!("{:X}{}", tf.frame.get_address(), if tf.frame.is_extended() { "x" } else { "" });
+        let data_str: String = data.iter().map(|b| format!("{:02X} ", b)).collect();
+        out.push_str(&format!("{:.6} {} {} Rx d {} {}\n", secs, channel, id_str, data.len(), data_str.trim_end()));
+    }
+    out
+}
+
+/// Parses a candump log back into timestamped frames.
+pub fn parse_candump(text: &str) -> Vec<TraceFrame> {
+    let mut out = Vec::new();
+    let mut first_secs: Option<f64> = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let close = match line.find(')') {
+            Some(i) => i,
+            None => continue
+        };
+        let ts_str = &line[1..close];
+        let secs: f64 = match ts_str.parse() {
+            Ok(s) => s,
+            Err(_) => continue
+        };
+        let rest = line[close + 1..].trim();
+        let mut parts = rest.splitn(2, ' ');
+        let _iface = parts.next();
+        let frame_str = match parts.next() {
+            Some(s) => s,
+            None => continue
+        };
+        let (id_str, data_str) = match frame_str.split_once('#') {
+            Some(x) => x,
+            None => continue
+        };
+        let id = match u32::from_str_radix(id_str, 16) {
+            Ok(v) => v,
+            Err(_) => continue
+        };
+        // candump zero-pads standard IDs to 3 hex digits and extended IDs to
+        // 8, so the field width - not the numeric value - tells them apart
+        // (a small extended ID would otherwise round-trip as standard).
+        let extended = id_str.len() > 3;
+        let mut data = Vec::new();
+        let data_str = data_str.trim();
+        let mut chars = data_str.chars();
+        while let (Some(hi), Some(lo)) = (chars.next(), chars.next()) {
+            if let Ok(b) = u8::from_str_radix(&format!("{}{}", hi, lo), 16) {
+                data.push(b);
+            }
+        }
+        let first = *first_secs.get_or_insert(secs);
+        out.push(TraceFrame {
+            offset: Duration::from_secs_f64((secs - first).max(0.0)),
+            frame: CanFrame::new(id, &data, extended)
+        });
+    }
+    out
+}
+
+/// Parses a Vector ASC log back into timestamped frames, skipping the header lines.
+pub fn parse_asc(text: &str) -> Vec<TraceFrame> {
+    let mut out = Vec::new();
+    let mut first_secs: Option<f64> = None;
+    for line in text.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 6 {
+            continue;
+        }
+        let secs: f64 = match parts[0].parse() {
+            Ok(s) => s,
+            Err(_) => continue
+        };
+        let id_field = parts[2];
+        let extended = id_field.ends_with('x');
+        let id_str = id_field.trim_end_matches('x');
+        let id = match u32::from_str_radix(id_str, 16) {
+            Ok(v) => v,
+            Err(_) => continue
+        };
+        let dlc: usize = match parts[5].parse() {
+            Ok(v) => v,
+            Err(_) => continue
+        };
+        let data: Vec<u8> = parts.iter().skip(6).take(dlc)
+            .filter_map(|b| u8::from_str_radix(b, 16).ok())
+            .collect();
+        let first = *first_secs.get_or_insert(secs);
+        out.push(TraceFrame {
+            offset: Duration::from_secs_f64((secs - first).max(0.0)),
+            frame: CanFrame::new(id, &data, extended)
+        });
+    }
+    out
+}
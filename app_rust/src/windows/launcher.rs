@@ -1,7 +1,10 @@
 use crate::passthru::{PassthruDevice, PassthruDrv};
+use crate::socketcan::SocketCanDevice;
 use iced::{pick_list, button, Text, Row, Element, Radio, Align, Column, PickList, Container, Length, Button};
 use crate::commapi::comm_api::{ComServerError, ComServer};
 use crate::commapi::passthru_api::PassthruApi;
+use crate::dyn_hw::DynHardware;
+use crate::privilege;
 use crate::windows::window::ApplicationError;
 use crate::windows::window::ApplicationError::DriverError;
 use crate::windows::launcher::LauncherMessage::LaunchRequested;
@@ -16,18 +19,25 @@ pub struct Launcher {
 
     device_names_dpdu: Vec<String>,
     selected_device_dpdu: String,
+
+    device_names_socketcan: Vec<String>,
+    selected_device_socketcan: String,
+
     api_selection: API,
 
     launch_state: button::State,
 
-    status_text: String
+    status_text: String,
+
+    launched_hardware: Option<DynHardware>
 
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum API {
     D_PDU,
-    Passthru
+    Passthru,
+    SocketCan
 }
 
 
@@ -47,6 +57,15 @@ impl ToString for ApplicationError {
 }
 
 type Result<T> = std::result::Result<T, ApplicationError>;
+
+/// Heuristic for telling "you're not allowed to touch this device" apart from
+/// "the device/driver doesn't exist", based on the error text J2534 DLLs
+/// typically surface (`ComServerError` has no dedicated permission variant).
+fn is_permission_error(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.contains("permission") || lower.contains("access denied") || lower.contains("access is denied")
+}
+
 impl Launcher {
 
     pub fn new() -> Self {
@@ -54,46 +73,85 @@ impl Launcher {
         let passthru_device_names: Vec<String> = passthru_devices.iter().map(|d| d.name.clone()).collect();
         let selected_passthru_device: String = passthru_device_names.get(0).map(|s| s.clone()).unwrap_or(String::new());
 
+        let socketcan_devices = SocketCanDevice::find_all().unwrap_or(Vec::new());
+        let socketcan_device_names: Vec<String> = socketcan_devices.iter().map(|d| d.name.clone()).collect();
+        let selected_socketcan_device: String = socketcan_device_names.get(0).map(|s| s.clone()).unwrap_or(String::new());
+
         Self {
             device_list_passthru: passthru_devices,
             device_names_passthru: passthru_device_names,
             selected_device_passthru: selected_passthru_device,
             device_names_dpdu: vec![],
             selected_device_dpdu: "".to_string(),
+            device_names_socketcan: socketcan_device_names,
+            selected_device_socketcan: selected_socketcan_device,
             selection: pick_list::State::default(),
             api_selection: API::Passthru,
             launch_state: button::State::default(),
-            status_text: "".into()
+            status_text: "".into(),
+            launched_hardware: None
         }
     }
 
+    /// Takes the hardware opened by the last successful SocketCAN
+    /// `LaunchRequested`, if any, so the caller can hand it to `CanTracerPage`
+    /// and move past this window. Only a SocketCAN launch stores hardware here;
+    /// a successful Passthru launch leaves this `None`.
+    pub fn take_launched_hardware(&mut self) -> Option<DynHardware> {
+        self.launched_hardware.take()
+    }
+
     pub fn update(&mut self, msg: LauncherMessage){
         match msg {
             LauncherMessage::SwitchAPI(api) => { self.api_selection = api },
             LauncherMessage::DeviceSelected(d) => {
-                if self.api_selection == API::Passthru {
-                    self.selected_device_passthru = d
-                } else {
-                    self.selected_device_dpdu = d
+                match self.api_selection {
+                    API::Passthru => self.selected_device_passthru = d,
+                    API::D_PDU => self.selected_device_dpdu = d,
+                    API::SocketCan => self.selected_device_socketcan = d
                 }
             }
             LauncherMessage::LaunchRequested => {
-                if self.api_selection == API::Passthru {
-                    match self.get_device_passthru() {
-                        Ok((details, driver)) => {
-                            let mut server = PassthruApi::new(details, driver);
-                            if let Err(e) = server.open_device() {
-                                self.status_text = e.to_string()
-                            } else {
-                                // Ready to launch OVD!
+                match self.api_selection {
+                    API::Passthru => {
+                        match self.get_device_passthru() {
+                            Ok((details, driver)) => {
+                                let mut server = PassthruApi::new(details, driver);
+                                if let Err(e) = server.open_device() {
+                                    // The J2534 DLL itself may refuse to open the device because
+                                    // the user lacks permission to access it (e.g. a udev rule
+                                    // restricting the USB device) - report that distinctly from a
+                                    // missing-device/driver-not-found failure.
+                                    self.status_text = if is_permission_error(&e.to_string()) {
+                                        format!("Permission error: {}", e)
+                                    } else {
+                                        e.to_string()
+                                    }
+                                } else {
+                                    // Ready to launch OVD!
+                                }
+                            },
+                            Err(x) => {
+                                self.status_text = x.to_string()
+                            }
+                        }
+                    },
+                    API::SocketCan => {
+                        if let Err(e) = privilege::ensure_can_privileges(&self.selected_device_socketcan) {
+                            self.status_text = format!("Permission error: {}", e);
+                        } else {
+                            match DynHardware::open_socketcan(&self.selected_device_socketcan) {
+                                Ok(hw) => {
+                                    self.launched_hardware = Some(hw);
+                                    // Ready to launch OVD!
+                                },
+                                Err(e) => self.status_text = format!("Could not open {}: {}", self.selected_device_socketcan, e)
                             }
-                        },
-                        Err(x) => {
-                            self.status_text = x.to_string()
                         }
+                    },
+                    API::D_PDU => {
+                        // TODO D-PDU Launching
                     }
-                } else {
-                    // TODO D-PDU Launching
                 }
             }
         }
@@ -114,6 +172,12 @@ impl Launcher {
                 Some(self.api_selection.clone()),
                 LauncherMessage::SwitchAPI
             ))
+            .push(Radio::new(
+                API::SocketCan,
+                "SocketCAN",
+                Some(self.api_selection.clone()),
+                LauncherMessage::SwitchAPI
+            ))
             .padding(20)
             .spacing(10)
             .align_items(Align::Center);
@@ -123,6 +187,27 @@ impl Launcher {
                 .push(selection)
                 .push(Text::new("D-PDU API is unimplemented, check back in a future release!"))
                 .spacing(10)
+        } else if self.api_selection == API::SocketCan {
+            let mut c = Column::new()
+                .spacing(10)
+                .padding(20)
+                .push(selection);
+            if self.selected_device_socketcan.len() == 0 {
+                // No SocketCAN interfaces
+                c = c.push(Text::new("No CAN interfaces found on this system (try `sudo ip link add dev vcan0 type vcan && sudo ip link set up vcan0`)"))
+            } else {
+                c = c.push(Text::new("Select CAN interface"))
+                    .push(PickList::new(
+                        &mut self.selection,
+                        &self.device_names_socketcan,
+                        Some(self.selected_device_socketcan.clone()),
+                        LauncherMessage::DeviceSelected))
+                    .push(Button::new(&mut self.launch_state, Text::new("Launch OVD!"))
+                        .on_press(LaunchRequested)
+                    )
+                    .push(Text::new(&self.status_text));
+            }
+            c.align_items(Align::Center)
         } else {
             let mut c = Column::new()
                 .spacing(10)